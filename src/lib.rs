@@ -1,104 +1,385 @@
 //! This crate provides a derive macro to override default serde::Deserialize behavior when deserializing adjacently tagged enum variants with fallback untagged value.
 //!
 //! Refer to `deserialize_enum_with_untagged_as_fallback` for details on how to use it
+//!
+//! The generated code buffers values through serde's `__private` content model
+//! (`serde::__private::de::{Content, ContentRefDeserializer}`). That module is internal
+//! to serde and not covered by its semver guarantees, so the `serde` dependency is pinned
+//! in `Cargo.toml`; bump the pin only after confirming the path still resolves.
 
 extern crate proc_macro;
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, Attribute, Data, DeriveInput, LitStr, Type};
+use quote::ToTokens;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Lit, LitStr, Type};
 
 #[derive(Debug)]
 struct EnumVariant {
     pub ident: String,
-    pub content_type: String,
+    pub discriminants: Vec<Discriminant>,
+    pub shape: VariantShape,
 }
 
-fn path_to_ident(path: &syn::Path) -> String {
-    if let Some(ident) = path.get_ident() {
-        ident.to_string()
-    } else {
-        path.segments
-            .iter()
-            .map(|segment| segment.ident.to_string())
-            .collect::<Vec<String>>()
-            .join("::")
+/// The field shape of a variant, mirroring the forms serde's adjacently-tagged
+/// representation handles.
+#[derive(Debug)]
+enum VariantShape {
+    /// `Variant` — no content.
+    Unit,
+    /// `Variant(Inner)` — a single newtype field.
+    Newtype(String),
+    /// `Variant(A, B, ..)` — a tuple with two or more fields.
+    Tuple(Vec<String>),
+    /// `Variant { a: A, b: B }` — named fields.
+    Struct(Vec<(String, String)>),
+}
+
+/// A tag spelling a variant accepts on the wire, carrying the kind it must be
+/// compared as. Every spelling (the variant's wire name after `rename` / `rename_all`,
+/// and each `alias`) is an integer or boolean when it parses as one, such as
+/// `#[serde(rename = "5")]` or `#[serde(alias = "true")]`; otherwise it is a string.
+#[derive(Debug)]
+enum Discriminant {
+    Str(String),
+    U64(u64),
+    I64(i64),
+    Bool(bool),
+}
+
+impl Discriminant {
+    // A `rename`/`alias` spelling is a boolean or integer when it parses as one,
+    // otherwise it stays a string.
+    fn from_spelling(spelling: &str) -> Discriminant {
+        if let Ok(value) = spelling.parse::<bool>() {
+            Discriminant::Bool(value)
+        } else if let Ok(value) = spelling.parse::<u64>() {
+            Discriminant::U64(value)
+        } else if let Ok(value) = spelling.parse::<i64>() {
+            Discriminant::I64(value)
+        } else {
+            Discriminant::Str(spelling.to_owned())
+        }
     }
 }
 
-fn has_serde_untagged_attribute(attributes: &[Attribute]) -> bool {
-    for attribute in attributes {
-        if attribute.path().is_ident("serde") {
-            let mut is_untagged = false;
-            attribute
-                .parse_nested_meta(|meta| {
-                    // #[serde(untagged))]
-                    if meta.path.is_ident("untagged") {
-                        is_untagged = true;
+/// serde's `#[serde(rename_all = "...")]` case conversions, implemented for the
+/// variant names this macro sees (which always arrive in `PascalCase`).
+#[derive(Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
+enum RenameRule {
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    fn from_wire(value: &str) -> Option<RenameRule> {
+        match value {
+            "lowercase" => Some(RenameRule::LowerCase),
+            "UPPERCASE" => Some(RenameRule::UpperCase),
+            "PascalCase" => Some(RenameRule::PascalCase),
+            "camelCase" => Some(RenameRule::CamelCase),
+            "snake_case" => Some(RenameRule::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnakeCase),
+            "kebab-case" => Some(RenameRule::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Some(RenameRule::ScreamingKebabCase),
+            _ => None,
+        }
+    }
+
+    fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::PascalCase => variant.to_owned(),
+            RenameRule::LowerCase => variant.to_ascii_lowercase(),
+            RenameRule::UpperCase => variant.to_ascii_uppercase(),
+            RenameRule::CamelCase => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::SnakeCase => {
+                let mut snake = String::new();
+                for (index, ch) in variant.char_indices() {
+                    if index > 0 && ch.is_ascii_uppercase() {
+                        snake.push('_');
                     }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnakeCase => {
+                RenameRule::SnakeCase.apply_to_variant(variant).to_ascii_uppercase()
+            }
+            RenameRule::KebabCase => {
+                RenameRule::SnakeCase.apply_to_variant(variant).replace('_', "-")
+            }
+            RenameRule::ScreamingKebabCase => {
+                RenameRule::KebabCase.apply_to_variant(variant).to_ascii_uppercase()
+            }
+        }
+    }
+}
 
-                    Ok(())
-                })
-                .unwrap();
+// Render a field type back to its source tokens so generic arguments (`Vec<u64>`,
+// `Option<T>`) survive into the generated shadow struct / tuple type. Going through
+// the raw idents would truncate `Vec<u64>` to `Vec` and fail to compile.
+fn type_to_string(ty: &Type) -> String {
+    ty.to_token_stream().to_string()
+}
 
-            if is_untagged {
-                return true;
+// Classify a variant's fields into the construction shape the generated code will emit.
+fn resolve_variant_shape(variant: &syn::Variant) -> VariantShape {
+    match &variant.fields {
+        syn::Fields::Unit => VariantShape::Unit,
+        syn::Fields::Unnamed(fields) => {
+            let types = fields
+                .unnamed
+                .iter()
+                .map(|field| type_to_string(&field.ty))
+                .collect::<Vec<_>>();
+            match types.len() {
+                0 => VariantShape::Unit,
+                1 => VariantShape::Newtype(types.into_iter().next().unwrap()),
+                _ => VariantShape::Tuple(types),
             }
         }
+        syn::Fields::Named(fields) => {
+            let named = fields
+                .named
+                .iter()
+                .map(|field| {
+                    let name = field
+                        .ident
+                        .as_ref()
+                        .expect("named field without an identifier")
+                        .to_string();
+                    (name, type_to_string(&field.ty))
+                })
+                .collect::<Vec<_>>();
+            VariantShape::Struct(named)
+        }
     }
-    false
 }
 
-fn get_tag_and_content_attributes(attributes: &[Attribute]) -> (String, String) {
-    let mut tag_attribute: Option<String> = None;
-    let mut content_attribute: Option<String> = None;
+// A serde attribute may carry keys this macro doesn't interpret (e.g. `deserialize_with`)
+// alongside the ones it does. Consume the value of an unrecognized `key = value` pair so
+// `parse_nested_meta` doesn't choke on the leftover tokens.
+fn swallow_unrecognized_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        let _: Lit = meta.value()?.parse()?;
+    }
+    Ok(())
+}
 
+// Walk every `#[serde(...)]` attribute, invoking `parse` for each nested meta and
+// collecting any parse error rather than aborting on the first one.
+fn for_each_serde_meta<F>(attributes: &[Attribute], errors: &mut Vec<syn::Error>, mut parse: F)
+where
+    F: FnMut(&syn::meta::ParseNestedMeta) -> syn::Result<bool>,
+{
     for attr in attributes {
         if attr.path().is_ident("serde") {
-            attr.parse_nested_meta(|meta| {
-                // #[serde(tag = "resourceTagField"))]
-                if meta.path.is_ident("tag") {
-                    let lit: LitStr = meta.value()?.parse()?;
-                    tag_attribute = Some(lit.value());
-                }
-                // #[serde(content = "resourceContentField"))]
-                else if meta.path.is_ident("content") {
-                    let lit: LitStr = meta.value()?.parse()?;
-                    content_attribute = Some(lit.value());
+            if let Err(error) = attr.parse_nested_meta(|meta| {
+                if parse(&meta)? {
+                    Ok(())
+                } else {
+                    swallow_unrecognized_value(&meta)
                 }
-
-                Ok(())
-            })
-            .unwrap();
+            }) {
+                errors.push(error);
+            }
         }
     }
+}
 
-    if tag_attribute.is_none() || content_attribute.is_none() {
-        panic!("Tag and content attributes must be set, ex. #[serde(tag = \"resourceType\", content = \"resource\")]");
+fn has_serde_untagged_attribute(attributes: &[Attribute], errors: &mut Vec<syn::Error>) -> bool {
+    let mut is_untagged = false;
+    for_each_serde_meta(attributes, errors, |meta| {
+        // #[serde(untagged)]
+        if meta.path.is_ident("untagged") {
+            is_untagged = true;
+            return Ok(true);
+        }
+        Ok(false)
+    });
+    is_untagged
+}
+
+fn get_tag_and_content_attributes(
+    attributes: &[Attribute],
+    errors: &mut Vec<syn::Error>,
+) -> (Option<String>, Option<String>) {
+    let mut tag_attribute: Option<String> = None;
+    let mut content_attribute: Option<String> = None;
+
+    for_each_serde_meta(attributes, errors, |meta| {
+        // #[serde(tag = "resourceTagField")]
+        if meta.path.is_ident("tag") {
+            let lit: LitStr = meta.value()?.parse()?;
+            tag_attribute = Some(lit.value());
+            return Ok(true);
+        }
+        // #[serde(content = "resourceContentField")]
+        if meta.path.is_ident("content") {
+            let lit: LitStr = meta.value()?.parse()?;
+            content_attribute = Some(lit.value());
+            return Ok(true);
+        }
+        Ok(false)
+    });
+
+    (tag_attribute, content_attribute)
+}
+
+// #[serde(rename_all = "snake_case")] on the container
+fn get_rename_all_rule(attributes: &[Attribute], errors: &mut Vec<syn::Error>) -> Option<RenameRule> {
+    let mut rule: Option<RenameRule> = None;
+
+    for_each_serde_meta(attributes, errors, |meta| {
+        if meta.path.is_ident("rename_all") {
+            let lit: LitStr = meta.value()?.parse()?;
+            rule = RenameRule::from_wire(&lit.value());
+            return Ok(true);
+        }
+        Ok(false)
+    });
+
+    rule
+}
+
+// #[serde(rename = "...")] on a single variant, overriding the container rule
+fn get_variant_rename(attributes: &[Attribute], errors: &mut Vec<syn::Error>) -> Option<String> {
+    let mut rename: Option<String> = None;
+
+    for_each_serde_meta(attributes, errors, |meta| {
+        if meta.path.is_ident("rename") {
+            let lit: LitStr = meta.value()?.parse()?;
+            rename = Some(lit.value());
+            return Ok(true);
+        }
+        Ok(false)
+    });
+
+    rename
+}
+
+// #[serde(alias = "...")] may appear more than once on a single variant
+fn get_variant_aliases(attributes: &[Attribute], errors: &mut Vec<syn::Error>) -> Vec<String> {
+    let mut aliases: Vec<String> = vec![];
+
+    for_each_serde_meta(attributes, errors, |meta| {
+        if meta.path.is_ident("alias") {
+            let lit: LitStr = meta.value()?.parse()?;
+            aliases.push(lit.value());
+            return Ok(true);
+        }
+        Ok(false)
+    });
+
+    aliases
+}
+
+fn discriminant_comparison(discriminant: &Discriminant) -> String {
+    let de = "serde::__private::de::ContentRefDeserializer::<D::Error>::new(resource_type)";
+    match discriminant {
+        Discriminant::Str(value) => format!(
+            r#"<String as serde::Deserialize>::deserialize({de}).ok().as_deref() == Some("{value}")"#
+        ),
+        Discriminant::U64(value) => {
+            format!(r#"<u64 as serde::Deserialize>::deserialize({de}).ok() == Some({value}u64)"#)
+        }
+        Discriminant::I64(value) => {
+            format!(r#"<i64 as serde::Deserialize>::deserialize({de}).ok() == Some({value}i64)"#)
+        }
+        Discriminant::Bool(value) => {
+            format!(r#"<bool as serde::Deserialize>::deserialize({de}).ok() == Some({value})"#)
+        }
     }
+}
 
-    (tag_attribute.unwrap(), content_attribute.unwrap())
+// Build the body that decodes `source` (a `&Content`) into the variant's inner shape and
+// returns the constructed `enum_name::ident`. `source` is the adjacent `content` field for
+// explicit variants and the whole buffered `content` for the untagged fallback.
+fn generate_construction(enum_name: &str, variant: &EnumVariant, source: &str) -> String {
+    let ident = &variant.ident;
+    let deserialize = |ty: &str| {
+        format!(
+            "<{ty} as serde::Deserialize>::deserialize(\n                serde::__private::de::ContentRefDeserializer::<D::Error>::new({source}),\n            )?"
+        )
+    };
+
+    match &variant.shape {
+        VariantShape::Unit => format!("Ok({enum_name}::{ident})"),
+        VariantShape::Newtype(content_type) => format!(
+            "let resource = {};\n            Ok({enum_name}::{ident}(resource))",
+            deserialize(content_type)
+        ),
+        VariantShape::Tuple(content_types) => {
+            let tuple_type = content_types.join(", ");
+            let splat = (0..content_types.len())
+                .map(|index| format!("resource.{index}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "let resource = {};\n            Ok({enum_name}::{ident}({splat}))",
+                deserialize(&format!("({tuple_type})"))
+            )
+        }
+        VariantShape::Struct(fields) => {
+            let shadow = format!("Shadow{ident}");
+            let shadow_fields = fields
+                .iter()
+                .map(|(name, ty)| format!("                {name}: {ty},"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let mapped_fields = fields
+                .iter()
+                .map(|(name, _)| format!("{name}: resource.{name}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                r#"#[derive(serde::Deserialize)]
+            struct {shadow} {{
+{shadow_fields}
+            }}
+            let resource = {};
+            Ok({enum_name}::{ident} {{ {mapped_fields} }})"#,
+                deserialize(&shadow)
+            )
+        }
+    }
 }
 
 fn generate_if_branch(enum_name: &str, variant: &EnumVariant) -> String {
+    let condition = variant
+        .discriminants
+        .iter()
+        .map(discriminant_comparison)
+        .collect::<Vec<_>>()
+        .join(" || ");
     format!(
         r#"
-        if resource_type == "{0}" {{
-            let resource = {1}::deserialize(resource.to_owned())
-                .map_err(|e| serde::de::Error::custom(e))?;
-            Ok({enum_name}::{0}(resource))
+        if {condition} {{
+            {body}
         }}
 "#,
-        variant.ident, variant.content_type
+        body = generate_construction(enum_name, variant, "resource"),
     )
 }
 
-// TODO: support other untagged types than serde_json::Value
 fn generate_else_branch(enum_name: &str, variant: &EnumVariant) -> String {
     format!(
         r#"       else {{
-            Ok({enum_name}::{}(resource.to_owned()))
+            {body}
         }}
     "#,
-        variant.ident
+        body = generate_construction(enum_name, variant, "&content"),
     )
 }
 
@@ -116,6 +397,19 @@ fn generate_if_else_tree(
     format!("{if_tree} {else_branch}")
 }
 
+// Merge every collected diagnostic into a single `compile_error!` token stream so the
+// user sees all of the problems with one build, each pointing at its own span.
+fn combine_errors(errors: Vec<syn::Error>) -> TokenStream {
+    let mut errors = errors.into_iter();
+    let mut combined = errors
+        .next()
+        .expect("combine_errors called without any errors");
+    for error in errors {
+        combined.combine(error);
+    }
+    combined.into_compile_error().into()
+}
+
 /// deserialize enum adjacently tagged enum without defaulting to untagged variant on failure
 ///
 /// Example:
@@ -187,58 +481,98 @@ fn generate_if_else_tree(
 #[proc_macro_derive(deserialize_enum_with_untagged_as_fallback)]
 pub fn derive_enum(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
+    let mut errors: Vec<syn::Error> = vec![];
 
-    let (tag_attribute, content_attribute) = get_tag_and_content_attributes(&input.attrs);
+    let (tag_attribute, content_attribute) =
+        get_tag_and_content_attributes(&input.attrs, &mut errors);
+    let rename_all_rule = get_rename_all_rule(&input.attrs, &mut errors);
 
     let enum_name = input.ident.to_string();
 
-    let enum_data = match input.data {
-        Data::Struct(_) => panic!("Unsupported type `struct`, must be `enum`"),
-        Data::Union(_) => panic!("Unsupported type `union`, must be `enum`"),
-        Data::Enum(value) => value,
+    let enum_data = match &input.data {
+        Data::Enum(value) => Some(value),
+        Data::Struct(_) => {
+            errors.push(syn::Error::new(
+                input.ident.span(),
+                "Unsupported type `struct`, must be `enum`",
+            ));
+            None
+        }
+        Data::Union(_) => {
+            errors.push(syn::Error::new(
+                input.ident.span(),
+                "Unsupported type `union`, must be `enum`",
+            ));
+            None
+        }
     };
 
+    if tag_attribute.is_none() || content_attribute.is_none() {
+        errors.push(syn::Error::new(
+            input.ident.span(),
+            "Tag and content attributes must be set, ex. #[serde(tag = \"resourceType\", content = \"resource\")]",
+        ));
+    }
+
     let mut variants: Vec<EnumVariant> = vec![];
     let mut untagged_variant: Option<EnumVariant> = None;
 
-    if enum_data.variants.is_empty() {
-        panic!("Enum variants are empty");
-    }
+    if let Some(enum_data) = enum_data {
+        if enum_data.variants.is_empty() {
+            errors.push(syn::Error::new(
+                input.ident.span(),
+                "Enum variants are empty",
+            ));
+        }
 
-    for variant in &enum_data.variants {
-        let variant_name = variant.ident.to_owned();
-        let mut variant_inner_type: Option<String> = None;
+        for variant in &enum_data.variants {
+            let variant_name = variant.ident.to_owned();
+            let is_untagged = has_serde_untagged_attribute(&variant.attrs, &mut errors);
 
-        let is_untagged = has_serde_untagged_attribute(&variant.attrs);
+            let shape = resolve_variant_shape(variant);
 
-        for field in &variant.fields {
-            let field_path = match &field.ty {
-                Type::Path(field_path) => field_path,
-                _ => continue,
+            let wire_name = match get_variant_rename(&variant.attrs, &mut errors) {
+                Some(rename) => rename,
+                None => match rename_all_rule {
+                    Some(rule) => rule.apply_to_variant(&variant_name.to_string()),
+                    None => variant_name.to_string(),
+                },
             };
-            variant_inner_type = Some(path_to_ident(&field_path.path));
-        }
 
-        match variant_inner_type {
-            Some(value) => {
-                let variant = EnumVariant {
-                    ident: variant_name.to_string(),
-                    content_type: value,
-                };
+            let mut discriminants = vec![Discriminant::from_spelling(&wire_name)];
+            discriminants.extend(
+                get_variant_aliases(&variant.attrs, &mut errors)
+                    .iter()
+                    .map(|alias| Discriminant::from_spelling(alias)),
+            );
 
-                if is_untagged {
-                    untagged_variant = Some(variant);
-                } else {
-                    variants.push(variant);
-                }
+            let enum_variant = EnumVariant {
+                ident: variant_name.to_string(),
+                discriminants,
+                shape,
+            };
+
+            if is_untagged {
+                untagged_variant = Some(enum_variant);
+            } else {
+                variants.push(enum_variant);
             }
-            None => panic!("Unable to resolve inner type of {variant_name}"),
+        }
+
+        if untagged_variant.is_none() {
+            errors.push(syn::Error::new(
+                input.ident.span(),
+                "No untagged variant specified, use serde::Deserialize instead",
+            ));
         }
     }
 
-    if untagged_variant.is_none() {
-        panic!("No untagged variant specified, use serde::Deserialize instead");
+    if !errors.is_empty() {
+        return combine_errors(errors);
     }
+
+    let tag_attribute = tag_attribute.unwrap();
+    let content_attribute = content_attribute.unwrap();
     let if_else_tree = generate_if_else_tree(&enum_name, &variants, &untagged_variant.unwrap());
 
     let output = format!(
@@ -248,16 +582,25 @@ impl<'de> serde::Deserialize<'de> for {enum_name} {{
     where
         D: serde::Deserializer<'de>,
     {{
-        let value = serde_json::Value::deserialize(deserializer)?;
-        
-        let resource_type = value
-            .get("{tag_attribute}")
-            .ok_or(serde::de::Error::custom("missing field `{tag_attribute}`"))?
-            .as_str()
-            .ok_or(serde::de::Error::custom("`{tag_attribute}` is not of type `string`"))?;
-            
-        let resource = value
-            .get("{content_attribute}")
+        let content = serde::__private::de::Content::deserialize(deserializer)?;
+
+        let map = match &content {{
+            serde::__private::de::Content::Map(map) => map,
+            _ => return Err(serde::de::Error::custom(
+                "expected a map to deserialize adjacently tagged enum",
+            )),
+        }};
+
+        let resource_type = map
+            .iter()
+            .find(|(key, _)| key.as_str() == Some("{tag_attribute}"))
+            .map(|(_, value)| value)
+            .ok_or(serde::de::Error::custom("missing field `{tag_attribute}`"))?;
+
+        let resource = map
+            .iter()
+            .find(|(key, _)| key.as_str() == Some("{content_attribute}"))
+            .map(|(_, value)| value)
             .ok_or(serde::de::Error::custom("missing field `{content_attribute}`"))?;
 
         {if_else_tree}
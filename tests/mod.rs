@@ -1,4 +1,4 @@
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use serde_sated::deserialize_enum_with_untagged_as_fallback;
 
@@ -152,31 +152,210 @@ fn test_rename() {
     assert!(matches!(result, ResourceStructWithRename::String(_)));
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Fallback {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+}
+
+#[derive(Debug, deserialize_enum_with_untagged_as_fallback, Serialize)]
+#[serde(tag = "resourceType", content = "resource")]
+pub enum ResourceStructWithTypedFallback {
+    Number(u64),
+    #[serde(untagged)]
+    Unknown(Fallback),
+}
+
+#[test]
+fn test_typed_untagged_fallback() {
+    let variant_fallback = json!({
+        "resourceType": "whatever",
+        "resource": {
+            "name": "fallback"
+        }
+    });
+    let result: ResourceStructWithTypedFallback =
+        serde_json::from_value(variant_fallback).unwrap();
+    assert!(matches!(
+        result,
+        ResourceStructWithTypedFallback::Unknown(Fallback { .. })
+    ));
+}
+
 #[derive(Debug, deserialize_enum_with_untagged_as_fallback, Serialize)]
 #[serde(tag = "resourceType", content = "resource")]
-pub enum ResourceStructWithDeserializeWith {
-    #[serde(deserialize_with = "always_returns_five")]
-    Number(u32),
+pub enum ResourceStructWithShapes {
+    Pair(u64, u64),
+    Inline { a: u64, b: u64 },
+    Nothing,
     #[serde(untagged)]
     Unknown(serde_json::Value),
 }
 
-fn always_returns_five<'de, D>(_deserializer: D) -> Result<u32, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    Ok(5u32)
+#[test]
+fn test_variant_shapes() {
+    let variant_pair = json!({
+        "resourceType": "Pair",
+        "resource": [2000, 3000]
+    });
+    let result: ResourceStructWithShapes = serde_json::from_value(variant_pair).unwrap();
+    assert!(matches!(result, ResourceStructWithShapes::Pair(2000, 3000)));
+
+    let variant_inline = json!({
+        "resourceType": "Inline",
+        "resource": {
+            "a": 2000,
+            "b": 3000
+        }
+    });
+    let result: ResourceStructWithShapes = serde_json::from_value(variant_inline).unwrap();
+    assert!(matches!(
+        result,
+        ResourceStructWithShapes::Inline { a: 2000, b: 3000 }
+    ));
+
+    let variant_unit = json!({
+        "resourceType": "Nothing",
+        "resource": null
+    });
+    let result: ResourceStructWithShapes = serde_json::from_value(variant_unit).unwrap();
+    assert!(matches!(result, ResourceStructWithShapes::Nothing));
+}
+
+#[derive(Debug, deserialize_enum_with_untagged_as_fallback, Serialize)]
+#[serde(tag = "resourceType", content = "resource")]
+pub enum ResourceStructWithGenericFields {
+    List(Vec<u64>),
+    Boxed { value: Option<String>, rest: Vec<u64> },
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
 }
 
 #[test]
-fn test_deserialize_with() {
-    let variant_string = json!({
-        "resourceType": "Number",
-        "resource": 1
+fn test_generic_fields() {
+    let variant_list = json!({
+        "resourceType": "List",
+        "resource": [2000, 3000]
+    });
+    let result: ResourceStructWithGenericFields = serde_json::from_value(variant_list).unwrap();
+    assert!(matches!(
+        result,
+        ResourceStructWithGenericFields::List(ref values) if values == &[2000, 3000]
+    ));
+
+    let variant_boxed = json!({
+        "resourceType": "Boxed",
+        "resource": {
+            "value": "text",
+            "rest": [1, 2]
+        }
+    });
+    let result: ResourceStructWithGenericFields = serde_json::from_value(variant_boxed).unwrap();
+    assert!(matches!(
+        result,
+        ResourceStructWithGenericFields::Boxed {
+            value: Some(_),
+            ref rest
+        } if rest == &[1, 2]
+    ));
+}
+
+#[derive(Debug, deserialize_enum_with_untagged_as_fallback, Serialize)]
+#[serde(tag = "resourceType", content = "resource")]
+pub enum ResourceStructWithNumericTag {
+    #[serde(rename = "5")]
+    Five(String),
+    #[serde(rename = "true")]
+    Flag(u64),
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+#[test]
+fn test_numeric_tag() {
+    let variant_five = json!({
+        "resourceType": 5,
+        "resource": "text"
+    });
+    let result: ResourceStructWithNumericTag = serde_json::from_value(variant_five).unwrap();
+    assert!(matches!(result, ResourceStructWithNumericTag::Five(_)));
+
+    let variant_unknown = json!({
+        "resourceType": 9,
+        "resource": "text"
+    });
+    let result: ResourceStructWithNumericTag = serde_json::from_value(variant_unknown).unwrap();
+    assert!(matches!(result, ResourceStructWithNumericTag::Unknown(_)));
+}
+
+#[test]
+fn test_bool_tag() {
+    let variant_flag = json!({
+        "resourceType": true,
+        "resource": 2000
+    });
+    let result: ResourceStructWithNumericTag = serde_json::from_value(variant_flag).unwrap();
+    assert!(matches!(result, ResourceStructWithNumericTag::Flag(2000)));
+
+    let variant_unknown = json!({
+        "resourceType": false,
+        "resource": 2000
+    });
+    let result: ResourceStructWithNumericTag = serde_json::from_value(variant_unknown).unwrap();
+    assert!(matches!(result, ResourceStructWithNumericTag::Unknown(_)));
+}
+
+#[derive(Debug, deserialize_enum_with_untagged_as_fallback, Serialize)]
+#[serde(tag = "resourceType", content = "resource", rename_all = "snake_case")]
+pub enum ResourceStructWithRenameAll {
+    SmallNumber(u64),
+    #[serde(rename = "explicit")]
+    BigNumber(u64),
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+#[test]
+fn test_rename_all() {
+    let variant_snake = json!({
+        "resourceType": "small_number",
+        "resource": 2000
+    });
+    let result: ResourceStructWithRenameAll = serde_json::from_value(variant_snake).unwrap();
+    assert!(matches!(
+        result,
+        ResourceStructWithRenameAll::SmallNumber(2000)
+    ));
+
+    let variant_explicit = json!({
+        "resourceType": "explicit",
+        "resource": 3000
     });
-    let result: ResourceStructWithDeserializeWith = serde_json::from_value(variant_string).unwrap();
+    let result: ResourceStructWithRenameAll = serde_json::from_value(variant_explicit).unwrap();
     assert!(matches!(
         result,
-        ResourceStructWithDeserializeWith::Number(5)
+        ResourceStructWithRenameAll::BigNumber(3000)
     ));
 }
+
+#[derive(Debug, deserialize_enum_with_untagged_as_fallback, Serialize)]
+#[serde(tag = "resourceType", content = "resource")]
+pub enum ResourceStructWithAlias {
+    #[serde(rename = "string", alias = "str", alias = "text")]
+    String(String),
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+#[test]
+fn test_alias() {
+    for tag in ["string", "str", "text"] {
+        let variant = json!({
+            "resourceType": tag,
+            "resource": "text"
+        });
+        let result: ResourceStructWithAlias = serde_json::from_value(variant).unwrap();
+        assert!(matches!(result, ResourceStructWithAlias::String(_)));
+    }
+}
+